@@ -1,30 +1,55 @@
+use crate::error::Error;
+use crate::integer::{I256, U256};
 use crate::solidity::Address;
 use crate::solidity::ConcreteSolidityType;
 use crate::solidity::Function;
 use crate::solidity::IntoType;
 use crate::solidity::SolidityType;
-use byteorder::{BigEndian, ByteOrder};
 use sha3::{Digest, Keccak256};
 use std::convert::TryInto;
 
 pub struct Builder<'a> {
     name: Option<String>,
+    /// The parameter types declared by [`Builder::from_signature`], checked against `params` by
+    /// [`Builder::build`]. `None` when the `Builder` was constructed with [`Builder::new`], in
+    /// which case no type-checking is performed.
+    expected: Option<Vec<SolidityType>>,
     pub(super) params: Vec<ConcreteSolidityType<'a>>,
 }
 
+impl<'a> Default for Builder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> Builder<'a> {
     pub fn new() -> Self {
         Builder {
             name: None,
+            expected: None,
             params: Vec::new(),
         }
     }
 
+    /// Parses a human-readable signature, e.g. `transfer(address,uint256)`, into a named
+    /// `Builder` whose declared parameter types are checked against the values pushed onto it
+    /// when [`Builder::build`] is called.
+    pub fn from_signature(signature: &str) -> Result<Self, Error> {
+        let (name, types) = crate::signature::parse(signature)?;
+        Ok(Builder {
+            name: Some(name),
+            expected: Some(types),
+            params: Vec::new(),
+        })
+    }
+
     pub fn name(mut self, name: String) -> Self {
         self.name = Some(name);
         self
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn add<F: IntoType<'a>>(mut self, value: F) -> Self {
         self.params.push(value.into_type());
         self
@@ -50,6 +75,46 @@ impl<'a> Builder<'a> {
         Ok(self)
     }
 
+    /// Accepts a decimal string, a `0x` hex string, a `[u8; 32]`, or a primitive unsigned
+    /// integer -- anything that fits in a `uint256` without being truncated.
+    pub fn add_u256<F: TryInto<U256>>(mut self, value: F) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_u256(value)?);
+        Ok(self)
+    }
+
+    pub fn add_u256_array<F: TryInto<U256> + Copy>(mut self, value: &[F]) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_u256_array(value)?);
+        Ok(self)
+    }
+
+    pub fn add_u256_fixed_array<F: TryInto<U256> + Copy>(
+        mut self,
+        value: &[F],
+    ) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_u256_fixed_array(value)?);
+        Ok(self)
+    }
+
+    /// Accepts a decimal string (optionally `-`-prefixed), a `0x` hex string, a `[u8; 32]`, or a
+    /// primitive signed integer -- anything that fits in an `int256` without being truncated.
+    pub fn add_i256<F: TryInto<I256>>(mut self, value: F) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_i256(value)?);
+        Ok(self)
+    }
+
+    pub fn add_i256_array<F: TryInto<I256> + Copy>(mut self, value: &[F]) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_i256_array(value)?);
+        Ok(self)
+    }
+
+    pub fn add_i256_fixed_array<F: TryInto<I256> + Copy>(
+        mut self,
+        value: &[F],
+    ) -> Result<Self, F::Error> {
+        self.params.push(crate::params::add_i256_fixed_array(value)?);
+        Ok(self)
+    }
+
     pub fn signature(&self) -> [u8; 4] {
         if let Some(name) = &self.name {
             let mut sig = [0; 4];
@@ -64,111 +129,164 @@ impl<'a> Builder<'a> {
                     .join(",")
             );
             hasher.input(&function);
-            sig.copy_from_slice(&hasher.result());
+            sig.copy_from_slice(&hasher.result()[..4]);
             sig
         } else {
             panic!("cannot calculate function signature without a name");
         }
     }
 
-    pub fn build(self) -> Vec<u8> {
+    /// Builds a tuple/struct parameter, e.g. `(address,uint256)`, out of the values pushed onto
+    /// a nested `Builder`.
+    pub fn add_tuple(mut self, tuple: Builder<'a>) -> Self {
+        self.params
+            .push(ConcreteSolidityType::Tuple(tuple.params));
+        self
+    }
+
+    /// Builds an unsized array parameter (`T[]`) out of already-built values, e.g. an array of
+    /// tuples or a multi-dimensional array whose elements are themselves arrays.
+    pub fn add_array(mut self, element: SolidityType, values: Vec<ConcreteSolidityType<'a>>) -> Self {
+        self.params.push(crate::solidity::array(element, values));
+        self
+    }
+
+    /// Builds a fixed-size array parameter (`T[k]`) out of already-built values.
+    pub fn add_fixed_array(
+        mut self,
+        element: SolidityType,
+        values: Vec<ConcreteSolidityType<'a>>,
+    ) -> Self {
+        self.params.push(crate::solidity::fixed_array(element, values));
+        self
+    }
+
+    /// Encodes the pushed parameters, prefixed with the 4-byte function selector if this
+    /// `Builder` has a name. If this `Builder` was constructed via [`Builder::from_signature`],
+    /// the pushed parameters are first checked against the declared types, returning
+    /// [`Error::TypeMismatch`] on the first mismatch (by arity or by type).
+    pub fn build(self) -> Result<Vec<u8>, Error> {
+        if let Some(expected) = &self.expected {
+            if expected.len() != self.params.len() {
+                return Err(Error::TypeMismatch {
+                    expected: format!("{} parameter(s)", expected.len()),
+                    found: format!("{} parameter(s)", self.params.len()),
+                });
+            }
+
+            for (expected, param) in expected.iter().zip(self.params.iter()) {
+                let found = param.declared_type();
+                if *expected != found {
+                    return Err(Error::TypeMismatch {
+                        expected: expected.to_string(),
+                        found: found.to_string(),
+                    });
+                }
+            }
+        }
+
         let name_offset = match self.name {
             None => 0,
             Some(_) => 4,
         };
 
-        let sig = if let Some(_) = self.name {
+        let sig = if self.name.is_some() {
             Some(self.signature())
         } else {
             None
         };
 
-        let total_len = self
-            .params
-            .iter()
-            .map(ConcreteSolidityType::required_byte_len)
-            .zip(self.params.iter().map(ConcreteSolidityType::is_dynamic))
-            .fold(
-                0,
-                |sum, (len, dynamic)| if dynamic { 32 + sum + len } else { sum + len },
-            );
-
-        let mut buf: Vec<u8> = vec![0; total_len + name_offset];
-
-        let mut offset: usize = self.params.len() * 32 + name_offset;
-
-        for (index, (dynamic, bytes)) in self
-            .params
-            .into_iter()
-            .map(ConcreteSolidityType::to_bytes)
-            .into_iter()
-            .enumerate()
-        {
-            if dynamic {
-                BigEndian::write_u64(
-                    &mut buf[index * 32 + 24 + name_offset..(index + 1) * 32 + name_offset],
-                    offset as u64,
-                );
-                buf[offset..offset + bytes.len()].copy_from_slice(&bytes);
-                offset += bytes.len()
-            } else {
-                buf[index * 32 + name_offset..(index + 1) * 32 + name_offset]
-                    .copy_from_slice(&bytes);
-            }
-        }
+        let mut buf: Vec<u8> = vec![0; name_offset];
+        buf.extend(crate::solidity::encode_sequence(self.params));
 
         if let Some(sig) = sig {
-            buf.copy_from_slice(&sig)
+            buf[..4].copy_from_slice(&sig)
         }
 
-        buf
+        Ok(buf)
     }
 }
 
-// This macro is used to generate all the `Builder::add_*()` methods for the various number types.
-#[macro_use]
+// This macro is used to generate all the `Builder::add_*()` methods for the various number
+// types; the actual `ConcreteSolidityType` construction lives in `crate::params`, shared with
+// `Event`'s equivalent methods.
 macro_rules! impl_solidity_function_for_builder {
-    ($ty: ty => $solidity: ident: $function: ident | $array: ident) => {
+    ($ty: ty => $function: ident | $array: ident | $fixed_array: ident) => {
         impl<'a> Builder<'a> {
             pub fn $function(mut self, value: $ty) -> Self {
-                self.params.push(ConcreteSolidityType::$solidity(
-                    SolidityType::$solidity,
-                    value,
-                ));
+                self.params.push(crate::params::$function(value));
                 self
             }
 
-            pub fn $array(mut self, value: &Vec<$ty>) -> Self {
-                use crate::solidity::SolidityArray;
-                let array = value
-                    .iter()
-                    .map(|value| ConcreteSolidityType::$solidity(SolidityType::$solidity, *value))
-                    .collect();
-
-                self.params.push(ConcreteSolidityType::Array(
-                    SolidityType::$solidity,
-                    SolidityArray {
-                        dimensions: 1,
-                        array,
-                    },
-                ));
+            pub fn $array(mut self, value: &[$ty]) -> Self {
+                self.params.push(crate::params::$array(value));
+                self
+            }
+
+            pub fn $fixed_array(mut self, value: &[$ty]) -> Self {
+                self.params.push(crate::params::$fixed_array(value));
                 self
             }
         }
     };
 }
 
-impl_solidity_function_for_builder!(i8 => I8: add_i8 | add_i8_array);
-impl_solidity_function_for_builder!(u8 => U8: add_u8 | add_u8_array);
-impl_solidity_function_for_builder!(i16 => I16: add_i16 | add_i16_array);
-impl_solidity_function_for_builder!(u16 => U16 : add_u16 | add_u16_array);
-impl_solidity_function_for_builder!(i32 => I32 : add_i32 | add_i32_array);
-impl_solidity_function_for_builder!(u32 => U32 : add_u32 | add_u32_array);
-impl_solidity_function_for_builder!(i64 => I64 : add_i64 | add_i64_array);
-impl_solidity_function_for_builder!(u64 => U64 : add_u64 | add_u64_array);
-impl_solidity_function_for_builder!(i128 => I128: add_i128 | add_i128_array);
-impl_solidity_function_for_builder!(u128 => U128: add_u128 | add_u128_array);
-impl_solidity_function_for_builder!(&'a [u8; 32] => I256: add_i256 | add_i256_array);
-impl_solidity_function_for_builder!(&'a str => String: add_string | add_string_array);
-impl_solidity_function_for_builder!(&'a [u8] => Bytes: add_bytes | add_bytes_array);
-impl_solidity_function_for_builder!(&'a [u8; 32] => U256: add_u256 | add_u256_array);
+impl_solidity_function_for_builder!(i8 => add_i8 | add_i8_array | add_i8_fixed_array);
+impl_solidity_function_for_builder!(u8 => add_u8 | add_u8_array | add_u8_fixed_array);
+impl_solidity_function_for_builder!(i16 => add_i16 | add_i16_array | add_i16_fixed_array);
+impl_solidity_function_for_builder!(u16 => add_u16 | add_u16_array | add_u16_fixed_array);
+impl_solidity_function_for_builder!(i32 => add_i32 | add_i32_array | add_i32_fixed_array);
+impl_solidity_function_for_builder!(u32 => add_u32 | add_u32_array | add_u32_fixed_array);
+impl_solidity_function_for_builder!(i64 => add_i64 | add_i64_array | add_i64_fixed_array);
+impl_solidity_function_for_builder!(u64 => add_u64 | add_u64_array | add_u64_fixed_array);
+impl_solidity_function_for_builder!(i128 => add_i128 | add_i128_array | add_i128_fixed_array);
+impl_solidity_function_for_builder!(u128 => add_u128 | add_u128_array | add_u128_fixed_array);
+impl_solidity_function_for_builder!(&'a str => add_string | add_string_array | add_string_fixed_array);
+impl_solidity_function_for_builder!(&'a [u8] => add_bytes | add_bytes_array | add_bytes_fixed_array);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_signature_round_trips_against_the_computed_selector() {
+        let encoded = Builder::from_signature("transfer(address,uint256)")
+            .unwrap()
+            .add_address([0u8; 20])
+            .unwrap()
+            .add_u256(42u64)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut hasher = Keccak256::new();
+        hasher.input("transfer(address,uint256)");
+        let selector = &hasher.result()[..4];
+
+        assert_eq!(&encoded[..4], selector);
+    }
+
+    #[test]
+    fn from_signature_rejects_wrong_arity() {
+        let result = Builder::from_signature("transfer(address,uint256)")
+            .unwrap()
+            .add_address([0u8; 20])
+            .unwrap()
+            .build();
+
+        assert!(matches!(result, Err(Error::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn from_signature_rejects_wrong_type_in_a_slot() {
+        let result = Builder::from_signature("transfer(address,uint256)")
+            .unwrap()
+            .add_u256(1u64)
+            .unwrap()
+            .add_u256(42u64)
+            .unwrap()
+            .build();
+
+        assert!(matches!(result, Err(Error::TypeMismatch { .. })));
+    }
+}
@@ -0,0 +1,296 @@
+//! The inverse of [`crate::builder::Builder::build`]: turns ABI-encoded bytes back into
+//! [`ConcreteSolidityType`] values given the expected [`SolidityType`] layout.
+use crate::error::Error;
+use crate::integer::{I256, U256};
+use crate::solidity::{Address, ConcreteSolidityType, Function, SolidityArray, SolidityType};
+use crate::words::{read_usize, WORD};
+use std::convert::TryInto;
+
+/// Decodes ABI-encoded output (or call) data against a declared list of [`SolidityType`]s.
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    skip_selector: bool,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Decoder {
+            bytes,
+            skip_selector: false,
+        }
+    }
+
+    /// Skip the leading 4-byte function selector before decoding, so the same `Decoder` can be
+    /// pointed at either a call's input data or a function's return data.
+    pub fn skip_selector(mut self) -> Self {
+        self.skip_selector = true;
+        self
+    }
+
+    pub fn decode(&self, types: &[SolidityType]) -> Result<Vec<ConcreteSolidityType<'a>>, Error> {
+        let data = if self.skip_selector {
+            take(self.bytes, 4)?.1
+        } else {
+            self.bytes
+        };
+
+        decode_sequence(data, types)
+    }
+}
+
+/// Decodes a self-contained head/tail region: each type consumes a head slot (a single offset
+/// word if dynamic, or its full static width otherwise — see [`SolidityType::static_width`]),
+/// followed by the tail data for the dynamic entries.
+fn decode_sequence<'a>(
+    region: &'a [u8],
+    types: &[SolidityType],
+) -> Result<Vec<ConcreteSolidityType<'a>>, Error> {
+    let mut cursor = 0;
+    types
+        .iter()
+        .map(|ty| {
+            let value = if ty.is_dynamic() {
+                let word = word_at(region, cursor)?;
+                let offset = read_usize(word);
+                cursor += WORD;
+                decode_dynamic(region, offset, ty)?
+            } else if let SolidityType::Tuple(members) = ty {
+                let width = ty.static_width();
+                let slot = take(region.get(cursor..).ok_or(Error::UnexpectedEndOfData {
+                    expected: cursor + width,
+                    available: region.len(),
+                })?, width)?
+                .0;
+                cursor += width;
+                ConcreteSolidityType::Tuple(decode_sequence(slot, members)?)
+            } else if let SolidityType::Array(element, Some(length)) = ty {
+                let width = ty.static_width();
+                let slot = take(region.get(cursor..).ok_or(Error::UnexpectedEndOfData {
+                    expected: cursor + width,
+                    available: region.len(),
+                })?, width)?
+                .0;
+                cursor += width;
+                let element_types: Vec<SolidityType> =
+                    std::iter::repeat_n((**element).clone(), *length).collect();
+                let array = decode_sequence(slot, &element_types)?;
+                ConcreteSolidityType::Array(
+                    (**element).clone(),
+                    SolidityArray { length: Some(*length), array },
+                )
+            } else {
+                let word = word_at(region, cursor)?;
+                cursor += WORD;
+                decode_static(word, ty)
+            };
+
+            Ok(value)
+        })
+        .collect()
+}
+
+fn decode_static<'a>(word: &'a [u8], ty: &SolidityType) -> ConcreteSolidityType<'a> {
+    match ty {
+        SolidityType::Address => {
+            let address: Address = word[WORD - 20..].try_into().expect("20 bytes");
+            ConcreteSolidityType::Address(SolidityType::Address, address)
+        }
+        SolidityType::Function => {
+            let function: Function = word[..24].try_into().expect("24 bytes");
+            ConcreteSolidityType::Function(SolidityType::Function, function)
+        }
+        SolidityType::I8 => ConcreteSolidityType::I8(SolidityType::I8, word[WORD - 1] as i8),
+        SolidityType::U8 => ConcreteSolidityType::U8(SolidityType::U8, word[WORD - 1]),
+        SolidityType::I16 => ConcreteSolidityType::I16(
+            SolidityType::I16,
+            i16::from_be_bytes(word[WORD - 2..].try_into().unwrap()),
+        ),
+        SolidityType::U16 => ConcreteSolidityType::U16(
+            SolidityType::U16,
+            u16::from_be_bytes(word[WORD - 2..].try_into().unwrap()),
+        ),
+        SolidityType::I32 => ConcreteSolidityType::I32(
+            SolidityType::I32,
+            i32::from_be_bytes(word[WORD - 4..].try_into().unwrap()),
+        ),
+        SolidityType::U32 => ConcreteSolidityType::U32(
+            SolidityType::U32,
+            u32::from_be_bytes(word[WORD - 4..].try_into().unwrap()),
+        ),
+        SolidityType::I64 => ConcreteSolidityType::I64(
+            SolidityType::I64,
+            i64::from_be_bytes(word[WORD - 8..].try_into().unwrap()),
+        ),
+        SolidityType::U64 => ConcreteSolidityType::U64(
+            SolidityType::U64,
+            u64::from_be_bytes(word[WORD - 8..].try_into().unwrap()),
+        ),
+        SolidityType::I128 => ConcreteSolidityType::I128(
+            SolidityType::I128,
+            i128::from_be_bytes(word[WORD - 16..].try_into().unwrap()),
+        ),
+        SolidityType::U128 => ConcreteSolidityType::U128(
+            SolidityType::U128,
+            u128::from_be_bytes(word[WORD - 16..].try_into().unwrap()),
+        ),
+        SolidityType::I256 => ConcreteSolidityType::I256(
+            SolidityType::I256,
+            I256(word.try_into().expect("32 bytes")),
+        ),
+        SolidityType::U256 => ConcreteSolidityType::U256(
+            SolidityType::U256,
+            U256(word.try_into().expect("32 bytes")),
+        ),
+        SolidityType::String | SolidityType::Bytes | SolidityType::Array(_, _) => {
+            unreachable!("dynamic types are decoded via decode_dynamic")
+        }
+        SolidityType::Tuple(_) => unreachable!("tuples are decoded via decode_sequence"),
+    }
+}
+
+fn decode_dynamic<'a>(
+    region: &'a [u8],
+    offset: usize,
+    ty: &SolidityType,
+) -> Result<ConcreteSolidityType<'a>, Error> {
+    let tail = region.get(offset..).ok_or(Error::InvalidOffset(offset))?;
+
+    match ty {
+        SolidityType::String => {
+            let bytes = decode_length_prefixed(tail)?;
+            let value = std::str::from_utf8(bytes)
+                .map_err(|_| Error::InvalidOffset(offset))?;
+            Ok(ConcreteSolidityType::String(SolidityType::String, value))
+        }
+        SolidityType::Bytes => {
+            let bytes = decode_length_prefixed(tail)?;
+            Ok(ConcreteSolidityType::Bytes(SolidityType::Bytes, bytes))
+        }
+        SolidityType::Array(element, None) => {
+            let count_word = word_at(tail, 0)?;
+            let count = read_usize(count_word);
+            let elements_region = take(tail, WORD)?.1;
+            check_array_count(elements_region, element, count)?;
+            let element_types: Vec<SolidityType> =
+                std::iter::repeat_n((**element).clone(), count).collect();
+            let array = decode_sequence(elements_region, &element_types)?;
+            Ok(ConcreteSolidityType::Array(
+                (**element).clone(),
+                SolidityArray { length: None, array },
+            ))
+        }
+        SolidityType::Array(element, Some(length)) => {
+            // A fixed-size array never carries a length prefix, even when its elements are
+            // dynamic, so the elements are decoded directly from the tail.
+            let element_types: Vec<SolidityType> =
+                std::iter::repeat_n((**element).clone(), *length).collect();
+            let array = decode_sequence(tail, &element_types)?;
+            Ok(ConcreteSolidityType::Array(
+                (**element).clone(),
+                SolidityArray { length: Some(*length), array },
+            ))
+        }
+        SolidityType::Tuple(members) => {
+            Ok(ConcreteSolidityType::Tuple(decode_sequence(tail, members)?))
+        }
+        _ => unreachable!("only dynamic types reach decode_dynamic"),
+    }
+}
+
+/// Rejects an array element count that the remaining buffer couldn't possibly satisfy, so a
+/// malformed/truncated length word (e.g. `0xffffffffffffffff`) fails with
+/// [`Error::UnexpectedEndOfData`] instead of attempting to allocate `count` elements up front.
+fn check_array_count(region: &[u8], element: &SolidityType, count: usize) -> Result<(), Error> {
+    let min_element_width = if element.is_dynamic() {
+        WORD
+    } else {
+        element.static_width()
+    };
+
+    let required = count
+        .checked_mul(min_element_width)
+        .ok_or(Error::UnexpectedEndOfData {
+            expected: usize::MAX,
+            available: region.len(),
+        })?;
+
+    if required > region.len() {
+        return Err(Error::UnexpectedEndOfData {
+            expected: required,
+            available: region.len(),
+        });
+    }
+
+    Ok(())
+}
+
+fn decode_length_prefixed(tail: &[u8]) -> Result<&[u8], Error> {
+    let length_word = word_at(tail, 0)?;
+    let length = read_usize(length_word);
+    let (_, data) = take(tail, WORD)?;
+    take(data, length).map(|(bytes, _)| bytes)
+}
+
+fn word_at(data: &[u8], at: usize) -> Result<&[u8], Error> {
+    take(data, at)?.1.get(..WORD).ok_or(Error::UnexpectedEndOfData {
+        expected: at + WORD,
+        available: data.len(),
+    })
+}
+
+/// Splits off the first `len` bytes of `data`, erroring if there aren't enough.
+fn take(data: &[u8], len: usize) -> Result<(&[u8], &[u8]), Error> {
+    if data.len() < len {
+        return Err(Error::UnexpectedEndOfData {
+            expected: len,
+            available: data.len(),
+        });
+    }
+
+    Ok(data.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+
+    #[test]
+    fn round_trips_through_builder() {
+        let encoded = Builder::new().add_u64(42).add_string("hi").build().unwrap();
+        let decoded = Decoder::new(&encoded)
+            .decode(&[SolidityType::U64, SolidityType::String])
+            .unwrap();
+
+        assert!(matches!(decoded[0], ConcreteSolidityType::U64(_, 42)));
+        assert!(matches!(decoded[1], ConcreteSolidityType::String(_, "hi")));
+    }
+
+    #[test]
+    fn empty_buffer_is_an_error_not_a_panic() {
+        let result = Decoder::new(&[]).decode(&[SolidityType::U64]);
+        assert!(matches!(result, Err(Error::UnexpectedEndOfData { .. })));
+    }
+
+    #[test]
+    fn truncated_dynamic_array_count_does_not_panic() {
+        // An offset word pointing at the count word right after it, a count word claiming
+        // u64::MAX elements and nothing else -- decoding this used to attempt an enormous
+        // up-front allocation instead of returning an error.
+        let mut data = vec![0u8; 64];
+        data[31] = 32;
+        data[32..64].copy_from_slice(&[0xff; 32]);
+
+        let types = vec![SolidityType::Array(Box::new(SolidityType::U256), None)];
+        let result = Decoder::new(&data).decode(&types);
+        assert!(matches!(result, Err(Error::UnexpectedEndOfData { .. })));
+    }
+
+    #[test]
+    fn offset_past_the_end_is_an_error() {
+        let mut data = vec![0u8; 32];
+        data[31] = 255;
+        let result = Decoder::new(&data).decode(&[SolidityType::String]);
+        assert!(matches!(result, Err(Error::InvalidOffset(255))));
+    }
+}
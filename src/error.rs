@@ -0,0 +1,47 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// An address did not decode to exactly 20 bytes.
+    InvalidAddress(String),
+    /// A function selector did not decode to exactly 24 bytes (20 byte address + 4 byte selector).
+    InvalidFunction(String),
+    /// The decoder ran out of bytes while reading a word, length, or offset.
+    UnexpectedEndOfData { expected: usize, available: usize },
+    /// A head offset pointed outside of the buffer being decoded, or the `string` it pointed at
+    /// was not valid UTF-8.
+    InvalidOffset(usize),
+    /// A numeric literal was malformed or did not fit in the target Solidity integer type.
+    ValueOutOfRange(String),
+    /// A human-readable function signature could not be parsed.
+    InvalidSignature(String),
+    /// A value pushed onto a `Builder` built from a signature didn't match the declared type.
+    TypeMismatch { expected: String, found: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidAddress(value) => write!(f, "invalid address: {}", value),
+            Error::InvalidFunction(value) => write!(f, "invalid function selector: {}", value),
+            Error::UnexpectedEndOfData {
+                expected,
+                available,
+            } => write!(
+                f,
+                "unexpected end of data: expected {} bytes, only {} available",
+                expected, available
+            ),
+            Error::InvalidOffset(offset) => write!(f, "offset {} is out of bounds", offset),
+            Error::ValueOutOfRange(value) => write!(f, "value out of range: {}", value),
+            Error::InvalidSignature(value) => write!(f, "invalid signature: {}", value),
+            Error::TypeMismatch { expected, found } => write!(
+                f,
+                "type mismatch: expected {}, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,305 @@
+//! Builds Ethereum event logs: a `topic0` signature hash, one topic per indexed parameter, and
+//! the ABI-encoded `data` for the remaining (non-indexed) parameters.
+use crate::integer::{I256, U256};
+use crate::solidity::Address;
+use crate::solidity::ConcreteSolidityType;
+use crate::solidity::Function;
+use crate::solidity::IntoType;
+use crate::solidity::SolidityType;
+use sha3::{Digest, Keccak256};
+use std::convert::TryInto;
+
+/// A decoded/ready-to-emit event log: the topics (`topic0` followed by one topic per indexed
+/// parameter, in declaration order) and the ABI-encoded data for the non-indexed parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Log {
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+pub struct Event<'a> {
+    name: String,
+    /// Each parameter paired with whether it's indexed, in declaration order — this order is
+    /// what the event signature hash and the topic/data split are both derived from.
+    params: Vec<(bool, ConcreteSolidityType<'a>)>,
+}
+
+impl<'a> Event<'a> {
+    pub fn new(name: String) -> Self {
+        Event {
+            name,
+            params: Vec::new(),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<F: IntoType<'a>>(mut self, value: F, indexed: bool) -> Self {
+        self.params.push((indexed, value.into_type()));
+        self
+    }
+
+    pub fn add_address(mut self, value: Address, indexed: bool) -> Self {
+        self.params
+            .push((indexed, ConcreteSolidityType::Address(SolidityType::Address, value)));
+        self
+    }
+
+    pub fn add_function(mut self, value: Function, indexed: bool) -> Self {
+        self.params.push((
+            indexed,
+            ConcreteSolidityType::Function(SolidityType::Function, value),
+        ));
+        self
+    }
+
+    /// Accepts a decimal string, a `0x` hex string, a `[u8; 32]`, or a primitive unsigned
+    /// integer -- anything that fits in a `uint256` without being truncated.
+    pub fn add_u256<F: TryInto<U256>>(mut self, value: F, indexed: bool) -> Result<Self, F::Error> {
+        self.params.push((indexed, crate::params::add_u256(value)?));
+        Ok(self)
+    }
+
+    pub fn add_u256_array<F: TryInto<U256> + Copy>(
+        mut self,
+        value: &[F],
+        indexed: bool,
+    ) -> Result<Self, F::Error> {
+        self.params
+            .push((indexed, crate::params::add_u256_array(value)?));
+        Ok(self)
+    }
+
+    pub fn add_u256_fixed_array<F: TryInto<U256> + Copy>(
+        mut self,
+        value: &[F],
+        indexed: bool,
+    ) -> Result<Self, F::Error> {
+        self.params
+            .push((indexed, crate::params::add_u256_fixed_array(value)?));
+        Ok(self)
+    }
+
+    /// Accepts a decimal string (optionally `-`-prefixed), a `0x` hex string, a `[u8; 32]`, or a
+    /// primitive signed integer -- anything that fits in an `int256` without being truncated.
+    pub fn add_i256<F: TryInto<I256>>(mut self, value: F, indexed: bool) -> Result<Self, F::Error> {
+        self.params.push((indexed, crate::params::add_i256(value)?));
+        Ok(self)
+    }
+
+    pub fn add_i256_array<F: TryInto<I256> + Copy>(
+        mut self,
+        value: &[F],
+        indexed: bool,
+    ) -> Result<Self, F::Error> {
+        self.params
+            .push((indexed, crate::params::add_i256_array(value)?));
+        Ok(self)
+    }
+
+    pub fn add_i256_fixed_array<F: TryInto<I256> + Copy>(
+        mut self,
+        value: &[F],
+        indexed: bool,
+    ) -> Result<Self, F::Error> {
+        self.params
+            .push((indexed, crate::params::add_i256_fixed_array(value)?));
+        Ok(self)
+    }
+
+    /// Adds a tuple/struct parameter out of the values pushed onto a nested
+    /// [`crate::builder::Builder`].
+    pub fn add_tuple(mut self, tuple: crate::builder::Builder<'a>, indexed: bool) -> Self {
+        self.params
+            .push((indexed, ConcreteSolidityType::Tuple(tuple.params)));
+        self
+    }
+
+    /// Adds an unsized array parameter (`T[]`) out of already-built values.
+    pub fn add_array(
+        mut self,
+        element: SolidityType,
+        values: Vec<ConcreteSolidityType<'a>>,
+        indexed: bool,
+    ) -> Self {
+        self.params
+            .push((indexed, crate::solidity::array(element, values)));
+        self
+    }
+
+    /// Adds a fixed-size array parameter (`T[k]`) out of already-built values.
+    pub fn add_fixed_array(
+        mut self,
+        element: SolidityType,
+        values: Vec<ConcreteSolidityType<'a>>,
+        indexed: bool,
+    ) -> Self {
+        self.params
+            .push((indexed, crate::solidity::fixed_array(element, values)));
+        self
+    }
+
+    /// Computes `topic0` (the keccak256 hash of the canonical event signature), splits the
+    /// pushed parameters into per-parameter topics (indexed) and a single ABI-encoded blob
+    /// (non-indexed), and returns the resulting [`Log`].
+    pub fn build(self) -> Log {
+        let signature = format!(
+            "{}({})",
+            self.name,
+            self.params
+                .iter()
+                .map(|(_, value)| value.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        );
+
+        let mut topics = vec![keccak256(signature.as_bytes())];
+        let mut data_params = Vec::new();
+
+        for (indexed, value) in self.params {
+            if indexed {
+                topics.push(topic(value));
+            } else {
+                data_params.push(value);
+            }
+        }
+
+        let data = crate::solidity::encode_sequence(data_params);
+        Log { topics, data }
+    }
+}
+
+/// The topic for a single indexed parameter: its plain 32-byte word for a scalar, or the
+/// keccak256 hash of its *raw* value for any reference type (string, bytes, array, or tuple),
+/// per the event-log spec -- not the head/tail ABI encoding `to_bytes` produces for the `data`
+/// field. Reference types are always hashed rather than inlined, whether or not the ABI
+/// considers them "dynamic", because a fixed-size array or tuple of static elements can still be
+/// wider than the single 32-byte word a topic holds. For `string`/`bytes` that means hashing the
+/// value's bytes directly with no length prefix or padding; for an array/tuple it means hashing
+/// the concatenated element/member encodings with no leading length word.
+fn topic(value: ConcreteSolidityType) -> [u8; 32] {
+    match value {
+        ConcreteSolidityType::String(_, value) => keccak256(value.as_bytes()),
+        ConcreteSolidityType::Bytes(_, value) => keccak256(value),
+        ConcreteSolidityType::Array(_, array) => {
+            keccak256(&crate::solidity::encode_sequence(array.array))
+        }
+        ConcreteSolidityType::Tuple(members) => keccak256(&crate::solidity::encode_sequence(members)),
+        _ => {
+            let (_, bytes) = value.to_bytes();
+            let mut word = [0u8; 32];
+            word.copy_from_slice(&bytes);
+            word
+        }
+    }
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    let mut hasher = Keccak256::new();
+    hasher.input(data);
+    hash.copy_from_slice(&hasher.result());
+    hash
+}
+
+// This macro is used to generate all the `Event::add_*()` methods for the various number types;
+// the actual `ConcreteSolidityType` construction lives in `crate::params`, shared with
+// `Builder`'s equivalent methods.
+macro_rules! impl_solidity_function_for_event {
+    ($ty: ty => $function: ident | $array: ident | $fixed_array: ident) => {
+        impl<'a> Event<'a> {
+            pub fn $function(mut self, value: $ty, indexed: bool) -> Self {
+                self.params.push((indexed, crate::params::$function(value)));
+                self
+            }
+
+            pub fn $array(mut self, value: &[$ty], indexed: bool) -> Self {
+                self.params.push((indexed, crate::params::$array(value)));
+                self
+            }
+
+            pub fn $fixed_array(mut self, value: &[$ty], indexed: bool) -> Self {
+                self.params
+                    .push((indexed, crate::params::$fixed_array(value)));
+                self
+            }
+        }
+    };
+}
+
+impl_solidity_function_for_event!(i8 => add_i8 | add_i8_array | add_i8_fixed_array);
+impl_solidity_function_for_event!(u8 => add_u8 | add_u8_array | add_u8_fixed_array);
+impl_solidity_function_for_event!(i16 => add_i16 | add_i16_array | add_i16_fixed_array);
+impl_solidity_function_for_event!(u16 => add_u16 | add_u16_array | add_u16_fixed_array);
+impl_solidity_function_for_event!(i32 => add_i32 | add_i32_array | add_i32_fixed_array);
+impl_solidity_function_for_event!(u32 => add_u32 | add_u32_array | add_u32_fixed_array);
+impl_solidity_function_for_event!(i64 => add_i64 | add_i64_array | add_i64_fixed_array);
+impl_solidity_function_for_event!(u64 => add_u64 | add_u64_array | add_u64_fixed_array);
+impl_solidity_function_for_event!(i128 => add_i128 | add_i128_array | add_i128_fixed_array);
+impl_solidity_function_for_event!(u128 => add_u128 | add_u128_array | add_u128_fixed_array);
+impl_solidity_function_for_event!(&'a str => add_string | add_string_array | add_string_fixed_array);
+impl_solidity_function_for_event!(&'a [u8] => add_bytes | add_bytes_array | add_bytes_fixed_array);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_string_topic_hashes_the_raw_bytes() {
+        let log = Event::new("Greeted".to_string())
+            .add_string("hello", true)
+            .build();
+
+        assert_eq!(log.topics[1], keccak256(b"hello"));
+    }
+
+    #[test]
+    fn indexed_static_topic_is_the_plain_word() {
+        let log = Event::new("Counted".to_string())
+            .add_u64(42, true)
+            .build();
+
+        let mut expected = [0u8; 32];
+        expected[24..].copy_from_slice(&42u64.to_be_bytes());
+        assert_eq!(log.topics[1], expected);
+    }
+
+    #[test]
+    fn indexed_dynamic_array_topic_has_no_length_prefix() {
+        let log = Event::new("Batched".to_string())
+            .add_u64_array(&[1, 2], true)
+            .build();
+
+        let elements = crate::builder::Builder::new()
+            .add_u64(1)
+            .add_u64(2)
+            .build()
+            .unwrap();
+        assert_eq!(log.topics[1], keccak256(&elements));
+    }
+
+    #[test]
+    fn non_indexed_params_go_into_data_not_topics() {
+        let log = Event::new("Noted".to_string())
+            .add_u64(7, false)
+            .build();
+
+        assert_eq!(log.topics.len(), 1);
+        assert!(!log.data.is_empty());
+    }
+
+    #[test]
+    fn indexed_static_fixed_array_wider_than_a_word_is_hashed_not_copied() {
+        // A `uint64[2]` is static (its element is static), but its 64-byte encoding doesn't fit
+        // in a single 32-byte topic word, so it must be hashed like any other reference type.
+        let log = Event::new("Paired".to_string())
+            .add_u64_fixed_array(&[1, 2], true)
+            .build();
+
+        let elements = crate::builder::Builder::new()
+            .add_u64(1)
+            .add_u64(2)
+            .build()
+            .unwrap();
+        assert_eq!(log.topics[1], keccak256(&elements));
+    }
+}
@@ -0,0 +1,266 @@
+//! Owned 256-bit words for `uint256`/`int256` parameters.
+//!
+//! [`crate::builder::Builder::add_u256`]/[`add_i256`](crate::builder::Builder::add_i256) used to
+//! only accept a hand-packed `&[u8; 32]`. [`U256`] and [`I256`] instead convert from decimal
+//! strings, `0x`-prefixed hex strings (arbitrary precision, so callers can pass a bignum's decimal
+//! rendering straight through) and Rust's primitive integers, rejecting anything that doesn't fit
+//! in 256 bits instead of silently truncating it.
+use crate::error::Error;
+use crate::words::{left_pad, left_pad_signed};
+use std::convert::TryFrom;
+
+/// A `uint256` value: a big-endian, zero-padded 32-byte word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U256(pub [u8; 32]);
+
+/// An `int256` value: a big-endian, two's-complement 32-byte word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct I256(pub [u8; 32]);
+
+impl From<[u8; 32]> for U256 {
+    fn from(value: [u8; 32]) -> Self {
+        U256(value)
+    }
+}
+
+impl From<[u8; 32]> for I256 {
+    fn from(value: [u8; 32]) -> Self {
+        I256(value)
+    }
+}
+
+impl TryFrom<&str> for U256 {
+    type Error = Error;
+
+    /// Parses a decimal string (e.g. `"1000000000000000000"`) or a `0x`-prefixed hex string
+    /// (e.g. `"0xde0b6b3a7640000"`) into a `uint256`, rejecting values that don't fit in 256 bits.
+    fn try_from(value: &str) -> Result<Self, Error> {
+        let magnitude = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(hex) => parse_hex(hex, value)?,
+            None => parse_decimal(value, value)?,
+        };
+
+        Ok(U256(magnitude))
+    }
+}
+
+impl TryFrom<&str> for I256 {
+    type Error = Error;
+
+    /// Parses a decimal string (e.g. `"-42"`) or a `0x`-prefixed hex string (e.g. `"-0x2a"`) into
+    /// an `int256`, rejecting magnitudes that don't fit in a signed 256-bit two's-complement word.
+    fn try_from(value: &str) -> Result<Self, Error> {
+        let (negative, rest) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+
+        let magnitude = match rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+            Some(hex) => parse_hex(hex, value)?,
+            None => parse_decimal(rest, value)?,
+        };
+
+        to_twos_complement(magnitude, negative, value).map(I256)
+    }
+}
+
+/// Parses `hex` (without its `0x` prefix) into a big-endian 32-byte word, most significant byte
+/// first, accepting an odd number of digits (e.g. `"abc"` is `0x0abc`).
+fn parse_hex(hex: &str, original: &str) -> Result<[u8; 32], Error> {
+    if hex.is_empty() || hex.len() > 64 {
+        return Err(Error::ValueOutOfRange(original.to_string()));
+    }
+
+    let digits = hex.as_bytes();
+    let mut word = [0u8; 32];
+    let mut end = digits.len();
+    let mut index = 32;
+
+    while end > 0 {
+        let start = end.saturating_sub(2);
+        let byte = std::str::from_utf8(&digits[start..end])
+            .ok()
+            .and_then(|chunk| u8::from_str_radix(chunk, 16).ok())
+            .ok_or_else(|| Error::ValueOutOfRange(original.to_string()))?;
+
+        index -= 1;
+        word[index] = byte;
+        end = start;
+    }
+
+    Ok(word)
+}
+
+/// Parses an arbitrary-precision decimal string into a big-endian 32-byte word via repeated
+/// multiply-by-ten-and-add, so callers can hand in a bignum's decimal rendering directly.
+fn parse_decimal(decimal: &str, original: &str) -> Result<[u8; 32], Error> {
+    if decimal.is_empty() || !decimal.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(Error::ValueOutOfRange(original.to_string()));
+    }
+
+    let mut word = [0u8; 32];
+    for digit in decimal.bytes() {
+        let mut carry = u16::from(digit - b'0');
+        for byte in word.iter_mut().rev() {
+            let product = u16::from(*byte) * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+
+        if carry != 0 {
+            return Err(Error::ValueOutOfRange(original.to_string()));
+        }
+    }
+
+    Ok(word)
+}
+
+/// Converts an unsigned magnitude into a signed two's-complement word, checking it fits in the
+/// `int256` range (`-2^255 <= value <= 2^255 - 1`) for the given sign.
+fn to_twos_complement(magnitude: [u8; 32], negative: bool, original: &str) -> Result<[u8; 32], Error> {
+    let is_min_magnitude = magnitude[0] == 0x80 && magnitude[1..].iter().all(|&byte| byte == 0);
+    let top_bit_set = magnitude[0] & 0x80 != 0;
+
+    if negative {
+        if top_bit_set && !is_min_magnitude {
+            return Err(Error::ValueOutOfRange(original.to_string()));
+        }
+
+        Ok(negate(magnitude))
+    } else {
+        if top_bit_set {
+            return Err(Error::ValueOutOfRange(original.to_string()));
+        }
+
+        Ok(magnitude)
+    }
+}
+
+/// Negates a big-endian 256-bit word in two's complement (`!word + 1`).
+fn negate(mut word: [u8; 32]) -> [u8; 32] {
+    for byte in word.iter_mut() {
+        *byte = !*byte;
+    }
+
+    let mut carry = 1u16;
+    for byte in word.iter_mut().rev() {
+        let sum = u16::from(*byte) + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+
+    word
+}
+
+// These macros generate the infallible `U256`/`I256` conversions for the primitive integer types
+// whose sign already matches (unsigned zero-extends into `U256`, signed sign-extends into
+// `I256`) -- there's no range to check, unlike the string conversions above.
+macro_rules! impl_from_unsigned {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for U256 {
+                fn from(value: $ty) -> Self {
+                    U256(left_pad(&value.to_be_bytes()))
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for I256 {
+                fn from(value: $ty) -> Self {
+                    I256(left_pad_signed(&value.to_be_bytes(), value < 0))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128);
+impl_from_signed!(i8, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_and_hex_agree() {
+        let decimal = U256::try_from("1000000000000000000").unwrap();
+        let hex = U256::try_from("0xde0b6b3a7640000").unwrap();
+        assert_eq!(decimal, hex);
+    }
+
+    #[test]
+    fn hex_accepts_odd_digit_count() {
+        assert_eq!(U256::try_from("0xabc").unwrap(), U256::try_from("0x0abc").unwrap());
+    }
+
+    #[test]
+    fn decimal_rejects_non_digits() {
+        assert!(U256::try_from("12a").is_err());
+    }
+
+    #[test]
+    fn decimal_rejects_overflow() {
+        assert!(U256::try_from("1".repeat(100).as_str()).is_err());
+    }
+
+    #[test]
+    fn hex_rejects_too_many_digits() {
+        assert!(U256::try_from(format!("0x{}", "f".repeat(65)).as_str()).is_err());
+    }
+
+    #[test]
+    fn negative_one_is_all_ff() {
+        assert_eq!(I256::try_from("-1").unwrap().0, [0xff; 32]);
+    }
+
+    #[test]
+    fn int256_min_boundary_is_accepted() {
+        let min = "-0x8000000000000000000000000000000000000000000000000000000000000000";
+        let value = I256::try_from(min).unwrap();
+        assert_eq!(value.0[0], 0x80);
+        assert!(value.0[1..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn int256_min_minus_one_is_out_of_range() {
+        let too_small = "-0x8000000000000000000000000000000000000000000000000000000000000001";
+        assert!(I256::try_from(too_small).is_err());
+    }
+
+    #[test]
+    fn int256_max_boundary_is_accepted() {
+        let max = "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        assert!(I256::try_from(max).is_ok());
+    }
+
+    #[test]
+    fn int256_max_plus_one_is_out_of_range() {
+        let too_big = "0x8000000000000000000000000000000000000000000000000000000000000000";
+        assert!(I256::try_from(too_big).is_err());
+    }
+
+    #[test]
+    fn u256_rejects_more_than_256_bits() {
+        let too_big = "0x10000000000000000000000000000000000000000000000000000000000000000";
+        assert!(U256::try_from(too_big).is_err());
+    }
+
+    #[test]
+    fn primitive_conversions_zero_and_sign_extend() {
+        let unsigned: U256 = 42u64.into();
+        assert_eq!(unsigned.0[..24], [0; 24]);
+        assert_eq!(unsigned.0[24..], 42u64.to_be_bytes());
+
+        let signed: I256 = (-1i32).into();
+        assert_eq!(signed.0, [0xff; 32]);
+
+        let positive: I256 = 42i32.into();
+        assert_eq!(positive.0[..28], [0; 28]);
+        assert_eq!(positive.0[28..], 42i32.to_be_bytes());
+    }
+}
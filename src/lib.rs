@@ -0,0 +1,16 @@
+pub mod builder;
+pub mod decoder;
+pub mod error;
+pub mod event;
+pub mod integer;
+mod params;
+mod signature;
+pub mod solidity;
+mod words;
+
+pub use builder::Builder;
+pub use decoder::Decoder;
+pub use error::Error;
+pub use event::{Event, Log};
+pub use integer::{I256, U256};
+pub use solidity::{Address, ConcreteSolidityType, Function, SolidityArray, SolidityType};
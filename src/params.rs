@@ -0,0 +1,145 @@
+//! Constructors that turn a raw value (a primitive integer, a string/byte slice, or anything
+//! convertible into a [`U256`]/[`I256`]) into the matching [`ConcreteSolidityType`].
+//!
+//! [`crate::builder::Builder`] and [`crate::event::Event`] both expose an `add_*` method per
+//! Solidity type; the only difference between the two is what they do with the resulting value
+//! (push it plainly, or pair it with an `indexed` flag), so the conversion itself lives here
+//! once and both types just call through to it.
+use crate::integer::{I256, U256};
+use crate::solidity::{ConcreteSolidityType, SolidityArray, SolidityType};
+use std::convert::TryInto;
+
+/// Accepts a decimal string, a `0x` hex string, a `[u8; 32]`, or a primitive unsigned integer --
+/// anything that fits in a `uint256` without being truncated.
+pub(crate) fn add_u256<'a, F: TryInto<U256>>(value: F) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let value: U256 = value.try_into()?;
+    Ok(ConcreteSolidityType::U256(SolidityType::U256, value))
+}
+
+pub(crate) fn add_u256_array<'a, F: TryInto<U256> + Copy>(
+    value: &[F],
+) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let array = value
+        .iter()
+        .map(|value| (*value).try_into().map(|value| ConcreteSolidityType::U256(SolidityType::U256, value)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConcreteSolidityType::Array(
+        SolidityType::U256,
+        SolidityArray {
+            length: None,
+            array,
+        },
+    ))
+}
+
+pub(crate) fn add_u256_fixed_array<'a, F: TryInto<U256> + Copy>(
+    value: &[F],
+) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let array = value
+        .iter()
+        .map(|value| (*value).try_into().map(|value| ConcreteSolidityType::U256(SolidityType::U256, value)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConcreteSolidityType::Array(
+        SolidityType::U256,
+        SolidityArray {
+            length: Some(array.len()),
+            array,
+        },
+    ))
+}
+
+/// Accepts a decimal string (optionally `-`-prefixed), a `0x` hex string, a `[u8; 32]`, or a
+/// primitive signed integer -- anything that fits in an `int256` without being truncated.
+pub(crate) fn add_i256<'a, F: TryInto<I256>>(value: F) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let value: I256 = value.try_into()?;
+    Ok(ConcreteSolidityType::I256(SolidityType::I256, value))
+}
+
+pub(crate) fn add_i256_array<'a, F: TryInto<I256> + Copy>(
+    value: &[F],
+) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let array = value
+        .iter()
+        .map(|value| (*value).try_into().map(|value| ConcreteSolidityType::I256(SolidityType::I256, value)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConcreteSolidityType::Array(
+        SolidityType::I256,
+        SolidityArray {
+            length: None,
+            array,
+        },
+    ))
+}
+
+pub(crate) fn add_i256_fixed_array<'a, F: TryInto<I256> + Copy>(
+    value: &[F],
+) -> Result<ConcreteSolidityType<'a>, F::Error> {
+    let array = value
+        .iter()
+        .map(|value| (*value).try_into().map(|value| ConcreteSolidityType::I256(SolidityType::I256, value)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConcreteSolidityType::Array(
+        SolidityType::I256,
+        SolidityArray {
+            length: Some(array.len()),
+            array,
+        },
+    ))
+}
+
+// This macro is used to generate the `add_*` constructors for the remaining (infallible) scalar
+// types: the fixed-width integers and `string`/`bytes`.
+macro_rules! impl_param_constructor {
+    ($ty: ty => $solidity: ident: $function: ident | $array: ident | $fixed_array: ident) => {
+        pub(crate) fn $function<'a>(value: $ty) -> ConcreteSolidityType<'a> {
+            ConcreteSolidityType::$solidity(SolidityType::$solidity, value)
+        }
+
+        pub(crate) fn $array<'a>(value: &[$ty]) -> ConcreteSolidityType<'a> {
+            let array = value
+                .iter()
+                .map(|value| ConcreteSolidityType::$solidity(SolidityType::$solidity, *value))
+                .collect();
+
+            ConcreteSolidityType::Array(
+                SolidityType::$solidity,
+                SolidityArray {
+                    length: None,
+                    array,
+                },
+            )
+        }
+
+        pub(crate) fn $fixed_array<'a>(value: &[$ty]) -> ConcreteSolidityType<'a> {
+            let array: Vec<_> = value
+                .iter()
+                .map(|value| ConcreteSolidityType::$solidity(SolidityType::$solidity, *value))
+                .collect();
+
+            ConcreteSolidityType::Array(
+                SolidityType::$solidity,
+                SolidityArray {
+                    length: Some(array.len()),
+                    array,
+                },
+            )
+        }
+    };
+}
+
+impl_param_constructor!(i8 => I8: add_i8 | add_i8_array | add_i8_fixed_array);
+impl_param_constructor!(u8 => U8: add_u8 | add_u8_array | add_u8_fixed_array);
+impl_param_constructor!(i16 => I16: add_i16 | add_i16_array | add_i16_fixed_array);
+impl_param_constructor!(u16 => U16 : add_u16 | add_u16_array | add_u16_fixed_array);
+impl_param_constructor!(i32 => I32 : add_i32 | add_i32_array | add_i32_fixed_array);
+impl_param_constructor!(u32 => U32 : add_u32 | add_u32_array | add_u32_fixed_array);
+impl_param_constructor!(i64 => I64 : add_i64 | add_i64_array | add_i64_fixed_array);
+impl_param_constructor!(u64 => U64 : add_u64 | add_u64_array | add_u64_fixed_array);
+impl_param_constructor!(i128 => I128: add_i128 | add_i128_array | add_i128_fixed_array);
+impl_param_constructor!(u128 => U128: add_u128 | add_u128_array | add_u128_fixed_array);
+impl_param_constructor!(&'a str => String: add_string | add_string_array | add_string_fixed_array);
+impl_param_constructor!(&'a [u8] => Bytes: add_bytes | add_bytes_array | add_bytes_fixed_array);
@@ -0,0 +1,303 @@
+//! Parses human-readable Solidity signatures, e.g. `transfer(address,uint256)` or
+//! `foo((uint256,bytes)[],string)`, into a function name and its declared [`SolidityType`]s.
+use crate::error::Error;
+use crate::solidity::SolidityType;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Parses a full function signature into its name and parameter types.
+pub(crate) fn parse(signature: &str) -> Result<(String, Vec<SolidityType>), Error> {
+    let open = signature.find('(').ok_or_else(|| {
+        Error::InvalidSignature(format!("missing '(' in signature: {}", signature))
+    })?;
+
+    if !signature.ends_with(')') {
+        return Err(Error::InvalidSignature(format!(
+            "missing closing ')' in signature: {}",
+            signature
+        )));
+    }
+
+    let name = signature[..open].to_string();
+    if name.is_empty() {
+        return Err(Error::InvalidSignature(format!(
+            "missing function name in signature: {}",
+            signature
+        )));
+    }
+
+    let body = &signature[open + 1..signature.len() - 1];
+    let types = parse_type_list(body)?;
+    Ok((name, types))
+}
+
+/// Parses a single type string, e.g. `uint256[3]` or `(address,uint256)[]`.
+pub(crate) fn parse_type(type_string: &str) -> Result<SolidityType, Error> {
+    let mut chars = type_string.char_indices().peekable();
+    let ty = parse_one_type(type_string, &mut chars)?;
+    if chars.peek().is_some() {
+        return Err(Error::InvalidSignature(format!(
+            "unexpected trailing characters in type: {}",
+            type_string
+        )));
+    }
+    Ok(ty)
+}
+
+fn parse_type_list(body: &str) -> Result<Vec<SolidityType>, Error> {
+    if body.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    split_top_level(body)?
+        .into_iter()
+        .map(parse_type)
+        .collect()
+}
+
+/// Splits `body` on top-level commas, i.e. commas that aren't nested inside a `(...)` tuple.
+fn split_top_level(body: &str) -> Result<Vec<&str>, Error> {
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, c) in body.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth < 0 {
+                    return Err(Error::InvalidSignature(format!(
+                        "unbalanced parentheses in: {}",
+                        body
+                    )));
+                }
+            }
+            ',' if depth == 0 => {
+                members.push(&body[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return Err(Error::InvalidSignature(format!(
+            "unbalanced parentheses in: {}",
+            body
+        )));
+    }
+
+    members.push(&body[start..]);
+    Ok(members)
+}
+
+/// Parses one type (a tuple or a base type name) followed by any number of `[]`/`[k]` array
+/// suffixes, out of `chars`, leaving anything after the last suffix unconsumed.
+fn parse_one_type(
+    source: &str,
+    chars: &mut Peekable<CharIndices>,
+) -> Result<SolidityType, Error> {
+    let mut ty = if chars.peek().map(|(_, c)| *c) == Some('(') {
+        parse_tuple(source, chars)?
+    } else {
+        parse_base_type(source, chars)?
+    };
+
+    while chars.peek().map(|(_, c)| *c) == Some('[') {
+        chars.next();
+        let mut digits = String::new();
+        while let Some(&(_, c)) = chars.peek() {
+            if c == ']' {
+                break;
+            }
+            digits.push(c);
+            chars.next();
+        }
+
+        match chars.next() {
+            Some((_, ']')) => {}
+            _ => {
+                return Err(Error::InvalidSignature(format!(
+                    "missing closing ']' in type: {}",
+                    source
+                )))
+            }
+        }
+
+        let length = if digits.is_empty() {
+            None
+        } else {
+            Some(digits.parse::<usize>().map_err(|_| {
+                Error::InvalidSignature(format!("invalid array length in type: {}", source))
+            })?)
+        };
+
+        ty = SolidityType::Array(Box::new(ty), length);
+    }
+
+    Ok(ty)
+}
+
+fn parse_tuple(
+    source: &str,
+    chars: &mut Peekable<CharIndices>,
+) -> Result<SolidityType, Error> {
+    chars.next(); // consume '('
+    let mut members = Vec::new();
+
+    loop {
+        match chars.peek().map(|(_, c)| *c) {
+            Some(')') => {
+                chars.next();
+                break;
+            }
+            Some(_) => {
+                members.push(parse_one_type(source, chars)?);
+                match chars.peek().map(|(_, c)| *c) {
+                    Some(',') => {
+                        chars.next();
+                    }
+                    Some(')') => {}
+                    _ => {
+                        return Err(Error::InvalidSignature(format!(
+                            "missing closing ')' in type: {}",
+                            source
+                        )))
+                    }
+                }
+            }
+            None => {
+                return Err(Error::InvalidSignature(format!(
+                    "missing closing ')' in type: {}",
+                    source
+                )))
+            }
+        }
+    }
+
+    Ok(SolidityType::Tuple(members))
+}
+
+fn parse_base_type(
+    source: &str,
+    chars: &mut Peekable<CharIndices>,
+) -> Result<SolidityType, Error> {
+    let start = match chars.peek() {
+        Some(&(index, _)) => index,
+        None => {
+            return Err(Error::InvalidSignature(format!(
+                "expected a type in: {}",
+                source
+            )))
+        }
+    };
+
+    let mut end = start;
+    while let Some(&(index, c)) = chars.peek() {
+        if c == '[' || c == ',' || c == ')' {
+            break;
+        }
+        end = index + c.len_utf8();
+        chars.next();
+    }
+
+    match &source[start..end] {
+        "address" => Ok(SolidityType::Address),
+        "function" => Ok(SolidityType::Function),
+        "int8" => Ok(SolidityType::I8),
+        "uint8" => Ok(SolidityType::U8),
+        "int16" => Ok(SolidityType::I16),
+        "uint16" => Ok(SolidityType::U16),
+        "int32" => Ok(SolidityType::I32),
+        "uint32" => Ok(SolidityType::U32),
+        "int64" => Ok(SolidityType::I64),
+        "uint64" => Ok(SolidityType::U64),
+        "int128" => Ok(SolidityType::I128),
+        "uint128" => Ok(SolidityType::U128),
+        "int256" => Ok(SolidityType::I256),
+        "uint256" => Ok(SolidityType::U256),
+        "string" => Ok(SolidityType::String),
+        "bytes" => Ok(SolidityType::Bytes),
+        other => Err(Error::InvalidSignature(format!(
+            "unknown type '{}' in: {}",
+            other, source
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_name_and_simple_types() {
+        let (name, types) = parse("transfer(address,uint256)").unwrap();
+        assert_eq!(name, "transfer");
+        assert_eq!(types, vec![SolidityType::Address, SolidityType::U256]);
+    }
+
+    #[test]
+    fn parses_no_argument_signature() {
+        let (name, types) = parse("pause()").unwrap();
+        assert_eq!(name, "pause");
+        assert!(types.is_empty());
+    }
+
+    #[test]
+    fn parses_tuple_and_nested_dynamic_array_of_tuples() {
+        let (_, types) = parse("foo((uint256,bytes)[],string)").unwrap();
+        assert_eq!(
+            types,
+            vec![
+                SolidityType::Array(
+                    Box::new(SolidityType::Tuple(vec![SolidityType::U256, SolidityType::Bytes])),
+                    None
+                ),
+                SolidityType::String,
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_fixed_and_multi_dimensional_arrays() {
+        let ty = parse_type("uint256[2][]").unwrap();
+        assert_eq!(
+            ty,
+            SolidityType::Array(
+                Box::new(SolidityType::Array(Box::new(SolidityType::U256), Some(2))),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_missing_opening_paren() {
+        assert!(parse("transfer address,uint256)").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_closing_paren() {
+        assert!(parse("transfer(address,uint256").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_function_name() {
+        assert!(parse("(address)").is_err());
+    }
+
+    #[test]
+    fn rejects_unbalanced_tuple_parens() {
+        assert!(parse("foo((address,uint256)").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_type() {
+        assert!(parse("foo(uint9000)").is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_characters_in_type() {
+        assert!(parse_type("uint256x").is_err());
+    }
+}
@@ -0,0 +1,530 @@
+//! The Solidity ABI value model shared by [`crate::builder::Builder`] and
+//! [`crate::decoder::Decoder`].
+use crate::error::Error;
+use crate::integer::{I256, U256};
+use crate::words::{left_pad, left_pad_signed, padded_len, WORD};
+use std::convert::TryFrom;
+
+/// A 20-byte Ethereum address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address(pub [u8; 20]);
+
+impl TryFrom<&[u8]> for Address {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() != 20 {
+            return Err(Error::InvalidAddress(format!(
+                "expected 20 bytes, found {}",
+                value.len()
+            )));
+        }
+
+        let mut address = [0u8; 20];
+        address.copy_from_slice(value);
+        Ok(Address(address))
+    }
+}
+
+impl TryFrom<[u8; 20]> for Address {
+    type Error = Error;
+
+    fn try_from(value: [u8; 20]) -> Result<Self, Self::Error> {
+        Ok(Address(value))
+    }
+}
+
+/// A Solidity `function` value: a 20-byte address followed by a 4-byte selector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Function(pub [u8; 24]);
+
+impl TryFrom<&[u8]> for Function {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        if value.len() != 24 {
+            return Err(Error::InvalidFunction(format!(
+                "expected 24 bytes, found {}",
+                value.len()
+            )));
+        }
+
+        let mut function = [0u8; 24];
+        function.copy_from_slice(value);
+        Ok(Function(function))
+    }
+}
+
+impl TryFrom<[u8; 24]> for Function {
+    type Error = Error;
+
+    fn try_from(value: [u8; 24]) -> Result<Self, Self::Error> {
+        Ok(Function(value))
+    }
+}
+
+/// The declared (as opposed to concrete/valued) shape of a Solidity ABI parameter.
+///
+/// This is what you get from parsing a signature string, or from describing the expected
+/// layout of a contract's return data so [`crate::decoder::Decoder`] knows how to walk it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolidityType {
+    Address,
+    Function,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+    I256,
+    U256,
+    String,
+    Bytes,
+    /// `Array(element, length)`: `length` is `None` for an unsized `T[]` and `Some(k)` for a
+    /// fixed-size `T[k]`. Multi-dimensional arrays (`T[2][4]`) are just an `Array` whose element
+    /// is itself an `Array`.
+    Array(Box<SolidityType>, Option<usize>),
+    Tuple(Vec<SolidityType>),
+}
+
+impl SolidityType {
+    /// Whether a value of this type is dynamic, i.e. encoded behind a head offset rather than
+    /// inline.
+    ///
+    /// A `T[k]` is static only when it has a fixed length *and* its element is static; an
+    /// unsized `T[]` is always dynamic regardless of its element.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            SolidityType::String | SolidityType::Bytes => true,
+            SolidityType::Array(element, length) => length.is_none() || element.is_dynamic(),
+            SolidityType::Tuple(members) => members.iter().any(SolidityType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// The number of head bytes a *static* value of this type occupies: 32 for scalars, the sum
+    /// of its members' static width for a static tuple, or `k` times its element's static width
+    /// for a static fixed array. Only meaningful when `!is_dynamic()`.
+    pub(crate) fn static_width(&self) -> usize {
+        match self {
+            SolidityType::Tuple(members) => members.iter().map(SolidityType::static_width).sum(),
+            SolidityType::Array(element, Some(length)) => length * element.static_width(),
+            _ => WORD,
+        }
+    }
+}
+
+impl std::fmt::Display for SolidityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SolidityType::Address => write!(f, "address"),
+            SolidityType::Function => write!(f, "function"),
+            SolidityType::I8 => write!(f, "int8"),
+            SolidityType::U8 => write!(f, "uint8"),
+            SolidityType::I16 => write!(f, "int16"),
+            SolidityType::U16 => write!(f, "uint16"),
+            SolidityType::I32 => write!(f, "int32"),
+            SolidityType::U32 => write!(f, "uint32"),
+            SolidityType::I64 => write!(f, "int64"),
+            SolidityType::U64 => write!(f, "uint64"),
+            SolidityType::I128 => write!(f, "int128"),
+            SolidityType::U128 => write!(f, "uint128"),
+            SolidityType::I256 => write!(f, "int256"),
+            SolidityType::U256 => write!(f, "uint256"),
+            SolidityType::String => write!(f, "string"),
+            SolidityType::Bytes => write!(f, "bytes"),
+            SolidityType::Array(element, Some(length)) => write!(f, "{}[{}]", element, length),
+            SolidityType::Array(element, None) => write!(f, "{}[]", element),
+            SolidityType::Tuple(members) => {
+                write!(f, "(")?;
+                for (index, member) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// A Solidity array value: the element type is carried by the enclosing
+/// [`ConcreteSolidityType::Array`] variant, this just holds the elements and the fixed length,
+/// if any (`None` for an unsized `T[]`, `Some(k)` for a fixed-size `T[k]`).
+#[derive(Debug, Clone)]
+pub struct SolidityArray<'a> {
+    pub length: Option<usize>,
+    pub array: Vec<ConcreteSolidityType<'a>>,
+}
+
+/// A trait for converting a Rust value directly into a [`ConcreteSolidityType`], used by
+/// [`crate::builder::Builder::add`].
+pub trait IntoType<'a> {
+    fn into_type(self) -> ConcreteSolidityType<'a>;
+}
+
+/// A Solidity ABI value paired with the [`SolidityType`] tag that produced it.
+#[derive(Debug, Clone)]
+pub enum ConcreteSolidityType<'a> {
+    Address(SolidityType, Address),
+    Function(SolidityType, Function),
+    I8(SolidityType, i8),
+    U8(SolidityType, u8),
+    I16(SolidityType, i16),
+    U16(SolidityType, u16),
+    I32(SolidityType, i32),
+    U32(SolidityType, u32),
+    I64(SolidityType, i64),
+    U64(SolidityType, u64),
+    I128(SolidityType, i128),
+    U128(SolidityType, u128),
+    I256(SolidityType, I256),
+    U256(SolidityType, U256),
+    String(SolidityType, &'a str),
+    Bytes(SolidityType, &'a [u8]),
+    Array(SolidityType, SolidityArray<'a>),
+    /// A Solidity tuple/struct. Unlike the other variants this carries no separate type tag:
+    /// its Solidity type is always derivable from the members themselves.
+    Tuple(Vec<ConcreteSolidityType<'a>>),
+}
+
+impl<'a> std::fmt::Display for ConcreteSolidityType<'a> {
+    /// The Solidity type string for this value, e.g. `uint256` or `(address,uint256)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConcreteSolidityType::Array(element, array) => match array.length {
+                Some(length) => write!(f, "{}[{}]", element, length),
+                None => write!(f, "{}[]", element),
+            },
+            ConcreteSolidityType::Tuple(members) => {
+                write!(f, "(")?;
+                for (index, member) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                write!(f, ")")
+            }
+            _ => write!(f, "{}", self.inner_type()),
+        }
+    }
+}
+
+impl<'a> ConcreteSolidityType<'a> {
+    fn inner_type(&self) -> &SolidityType {
+        match self {
+            ConcreteSolidityType::Address(ty, _)
+            | ConcreteSolidityType::Function(ty, _)
+            | ConcreteSolidityType::I8(ty, _)
+            | ConcreteSolidityType::U8(ty, _)
+            | ConcreteSolidityType::I16(ty, _)
+            | ConcreteSolidityType::U16(ty, _)
+            | ConcreteSolidityType::I32(ty, _)
+            | ConcreteSolidityType::U32(ty, _)
+            | ConcreteSolidityType::I64(ty, _)
+            | ConcreteSolidityType::U64(ty, _)
+            | ConcreteSolidityType::I128(ty, _)
+            | ConcreteSolidityType::U128(ty, _)
+            | ConcreteSolidityType::I256(ty, _)
+            | ConcreteSolidityType::U256(ty, _)
+            | ConcreteSolidityType::String(ty, _)
+            | ConcreteSolidityType::Bytes(ty, _)
+            | ConcreteSolidityType::Array(ty, _) => ty,
+            ConcreteSolidityType::Tuple(_) => unreachable!("tuples are displayed directly"),
+        }
+    }
+
+    /// The full [`SolidityType`] this value was built from, recursing into tuple members and
+    /// array elements. Used to type-check a [`crate::builder::Builder`] built from a parsed
+    /// signature against the values actually pushed onto it.
+    pub(crate) fn declared_type(&self) -> SolidityType {
+        match self {
+            ConcreteSolidityType::Array(element, array) => {
+                SolidityType::Array(Box::new(element.clone()), array.length)
+            }
+            ConcreteSolidityType::Tuple(members) => {
+                SolidityType::Tuple(members.iter().map(ConcreteSolidityType::declared_type).collect())
+            }
+            _ => self.inner_type().clone(),
+        }
+    }
+
+    /// Whether this value is dynamic, i.e. encoded behind a head offset rather than inline.
+    ///
+    /// A fixed-size array (`array.length` is `Some`) is dynamic only if its element type is;
+    /// an unsized array is always dynamic.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ConcreteSolidityType::String(_, _) | ConcreteSolidityType::Bytes(_, _) => true,
+            ConcreteSolidityType::Array(element, array) => {
+                array.length.is_none() || element.is_dynamic()
+            }
+            ConcreteSolidityType::Tuple(members) => {
+                members.iter().any(ConcreteSolidityType::is_dynamic)
+            }
+            _ => false,
+        }
+    }
+
+    /// The number of bytes this value occupies: 32 for static scalars, the sum of the members'
+    /// width for a static tuple or fixed array, and the length of the tail (head slot excluded)
+    /// for dynamic values.
+    pub fn required_byte_len(&self) -> usize {
+        match self {
+            ConcreteSolidityType::String(_, value) => WORD + padded_len(value.len()),
+            ConcreteSolidityType::Bytes(_, value) => WORD + padded_len(value.len()),
+            ConcreteSolidityType::Array(_, array) => match array.length {
+                // A fixed-size array has no length prefix; an unsized one does.
+                Some(_) => encoded_sequence_len(&array.array),
+                None => WORD + encoded_sequence_len(&array.array),
+            },
+            ConcreteSolidityType::Tuple(members) => encoded_sequence_len(members),
+            _ => WORD,
+        }
+    }
+
+    /// Encodes this value, returning whether it is dynamic along with its encoded bytes.
+    ///
+    /// For static values the bytes are their full inline encoding (one 32-byte word for a
+    /// scalar, several for a static tuple or fixed array), meant to be copied straight into the
+    /// head. For dynamic values the bytes are the tail: for `string`, `bytes` and unsized arrays
+    /// a length word followed by the (possibly nested) encoded data, padded up to a 32-byte
+    /// boundary; for dynamic tuples and fixed arrays their own head/tail region with no length
+    /// word.
+    pub fn to_bytes(self) -> (bool, Vec<u8>) {
+        match self {
+            ConcreteSolidityType::Address(_, Address(value)) => {
+                (false, left_pad(&value).to_vec())
+            }
+            ConcreteSolidityType::Function(_, Function(value)) => {
+                let mut word = [0u8; WORD];
+                word[..24].copy_from_slice(&value);
+                (false, word.to_vec())
+            }
+            ConcreteSolidityType::I8(_, value) => {
+                (false, left_pad_signed(&value.to_be_bytes(), value < 0).to_vec())
+            }
+            ConcreteSolidityType::U8(_, value) => (false, left_pad(&value.to_be_bytes()).to_vec()),
+            ConcreteSolidityType::I16(_, value) => {
+                (false, left_pad_signed(&value.to_be_bytes(), value < 0).to_vec())
+            }
+            ConcreteSolidityType::U16(_, value) => (false, left_pad(&value.to_be_bytes()).to_vec()),
+            ConcreteSolidityType::I32(_, value) => {
+                (false, left_pad_signed(&value.to_be_bytes(), value < 0).to_vec())
+            }
+            ConcreteSolidityType::U32(_, value) => (false, left_pad(&value.to_be_bytes()).to_vec()),
+            ConcreteSolidityType::I64(_, value) => {
+                (false, left_pad_signed(&value.to_be_bytes(), value < 0).to_vec())
+            }
+            ConcreteSolidityType::U64(_, value) => (false, left_pad(&value.to_be_bytes()).to_vec()),
+            ConcreteSolidityType::I128(_, value) => {
+                (false, left_pad_signed(&value.to_be_bytes(), value < 0).to_vec())
+            }
+            ConcreteSolidityType::U128(_, value) => (false, left_pad(&value.to_be_bytes()).to_vec()),
+            ConcreteSolidityType::I256(_, value) => (false, value.0.to_vec()),
+            ConcreteSolidityType::U256(_, value) => (false, value.0.to_vec()),
+            ConcreteSolidityType::String(_, value) => {
+                (true, encode_length_prefixed(value.as_bytes()))
+            }
+            ConcreteSolidityType::Bytes(_, value) => (true, encode_length_prefixed(value)),
+            ConcreteSolidityType::Array(element, array) => match array.length {
+                // A fixed-size array is only dynamic if its element is; either way it has no
+                // length prefix since the size is already known from the type.
+                Some(_) => (element.is_dynamic(), encode_sequence(array.array)),
+                None => {
+                    let mut bytes = left_pad(&(array.array.len() as u64).to_be_bytes()).to_vec();
+                    bytes.extend(encode_sequence(array.array));
+                    (true, bytes)
+                }
+            },
+            ConcreteSolidityType::Tuple(members) => {
+                // A static tuple is the plain concatenation of its (static) members with no
+                // offset word of its own; a dynamic tuple is its own self-contained head/tail
+                // region. `encode_sequence` produces exactly that in both cases.
+                let dynamic = members.iter().any(ConcreteSolidityType::is_dynamic);
+                (dynamic, encode_sequence(members))
+            }
+        }
+    }
+}
+
+/// Builds an unsized dynamic array value (`T[]`) out of already-built elements, e.g. to nest an
+/// array of tuples or a multi-dimensional array inside a [`crate::builder::Builder`].
+pub fn array<'a>(
+    element: SolidityType,
+    values: Vec<ConcreteSolidityType<'a>>,
+) -> ConcreteSolidityType<'a> {
+    ConcreteSolidityType::Array(
+        element,
+        SolidityArray {
+            length: None,
+            array: values,
+        },
+    )
+}
+
+/// Builds a fixed-size array value (`T[k]`, with `k = values.len()`) out of already-built
+/// elements.
+pub fn fixed_array<'a>(
+    element: SolidityType,
+    values: Vec<ConcreteSolidityType<'a>>,
+) -> ConcreteSolidityType<'a> {
+    let length = Some(values.len());
+    ConcreteSolidityType::Array(
+        element,
+        SolidityArray {
+            length,
+            array: values,
+        },
+    )
+}
+
+fn encode_length_prefixed(data: &[u8]) -> Vec<u8> {
+    let mut bytes = left_pad(&(data.len() as u64).to_be_bytes()).to_vec();
+    bytes.extend_from_slice(data);
+    bytes.resize(WORD + padded_len(data.len()), 0);
+    bytes
+}
+
+/// The number of head bytes `item` occupies in its parent's head/tail region: a single offset
+/// word for dynamic items, or its full inline width for static ones (32 bytes for a scalar, or
+/// the concatenated width of its members for a static tuple).
+fn head_width(item: &ConcreteSolidityType) -> usize {
+    if item.is_dynamic() {
+        WORD
+    } else {
+        item.required_byte_len()
+    }
+}
+
+/// The total encoded length of a head/tail region for `items`: the head width of each item (see
+/// [`head_width`]) plus the tail bytes for any dynamic items among them.
+pub(crate) fn encoded_sequence_len(items: &[ConcreteSolidityType]) -> usize {
+    let head_len: usize = items.iter().map(head_width).sum();
+    let tail_len: usize = items
+        .iter()
+        .filter(|item| item.is_dynamic())
+        .map(ConcreteSolidityType::required_byte_len)
+        .sum();
+    head_len + tail_len
+}
+
+/// Encodes `items` as a self-contained head/tail region: each item contributes its head width
+/// (see [`head_width`]) inline for static items, or an offset relative to the start of this
+/// region for dynamic items, followed by the tail data for the dynamic items in order.
+pub(crate) fn encode_sequence(items: Vec<ConcreteSolidityType>) -> Vec<u8> {
+    let head_len: usize = items.iter().map(head_width).sum();
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    let mut offset = head_len;
+
+    for item in items {
+        let dynamic = item.is_dynamic();
+        let (_, bytes) = item.to_bytes();
+        if dynamic {
+            let mut word = [0u8; WORD];
+            crate::words::write_usize(&mut word, offset);
+            head.extend_from_slice(&word);
+            tail.extend_from_slice(&bytes);
+            offset += bytes.len();
+        } else {
+            head.extend_from_slice(&bytes);
+        }
+    }
+
+    head.extend(tail);
+    head
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::Builder;
+    use crate::decoder::Decoder;
+
+    #[test]
+    fn tuple_round_trips_through_builder_and_decoder() {
+        let tuple = Builder::new().add_u64(1).add_string("hi");
+        let encoded = Builder::new().add_tuple(tuple).build().unwrap();
+
+        let decoded = Decoder::new(&encoded)
+            .decode(&[SolidityType::Tuple(vec![SolidityType::U64, SolidityType::String])])
+            .unwrap();
+
+        match &decoded[0] {
+            ConcreteSolidityType::Tuple(members) => {
+                assert!(matches!(members[0], ConcreteSolidityType::U64(_, 1)));
+                assert!(matches!(members[1], ConcreteSolidityType::String(_, "hi")));
+            }
+            other => panic!("expected a tuple, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fixed_size_array_round_trips() {
+        let values = vec![
+            ConcreteSolidityType::U64(SolidityType::U64, 1),
+            ConcreteSolidityType::U64(SolidityType::U64, 2),
+            ConcreteSolidityType::U64(SolidityType::U64, 3),
+        ];
+        let encoded = Builder::new().add_fixed_array(SolidityType::U64, values).build().unwrap();
+
+        let decoded = Decoder::new(&encoded)
+            .decode(&[SolidityType::Array(Box::new(SolidityType::U64), Some(3))])
+            .unwrap();
+
+        match &decoded[0] {
+            ConcreteSolidityType::Array(_, array) => {
+                assert_eq!(array.length, Some(3));
+                assert!(matches!(array.array[0], ConcreteSolidityType::U64(_, 1)));
+                assert!(matches!(array.array[2], ConcreteSolidityType::U64(_, 3)));
+            }
+            other => panic!("expected an array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn multi_dimensional_array_round_trips() {
+        let inner_a = crate::solidity::array(
+            SolidityType::U64,
+            vec![
+                ConcreteSolidityType::U64(SolidityType::U64, 1),
+                ConcreteSolidityType::U64(SolidityType::U64, 2),
+            ],
+        );
+        let inner_b = crate::solidity::array(
+            SolidityType::U64,
+            vec![ConcreteSolidityType::U64(SolidityType::U64, 3)],
+        );
+        let encoded = Builder::new()
+            .add_array(SolidityType::Array(Box::new(SolidityType::U64), None), vec![inner_a, inner_b])
+            .build()
+            .unwrap();
+
+        let ty = SolidityType::Array(
+            Box::new(SolidityType::Array(Box::new(SolidityType::U64), None)),
+            None,
+        );
+        let decoded = Decoder::new(&encoded).decode(&[ty]).unwrap();
+
+        match &decoded[0] {
+            ConcreteSolidityType::Array(_, outer) => {
+                assert_eq!(outer.length, None);
+                match &outer.array[0] {
+                    ConcreteSolidityType::Array(_, inner) => {
+                        assert!(matches!(inner.array[1], ConcreteSolidityType::U64(_, 2)));
+                    }
+                    other => panic!("expected a nested array, got {:?}", other),
+                }
+            }
+            other => panic!("expected an array, got {:?}", other),
+        }
+    }
+}
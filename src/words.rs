@@ -0,0 +1,44 @@
+//! Shared helpers for reading and writing 32-byte ABI words.
+//!
+//! Both `Builder::build` and `Decoder::decode` walk the same head/tail layout, just in
+//! opposite directions, so the low level word plumbing lives here instead of being
+//! duplicated in both places.
+use byteorder::{BigEndian, ByteOrder};
+
+pub(crate) const WORD: usize = 32;
+
+/// Left-pads `bytes` into a single 32-byte word, right-aligned.
+pub(crate) fn left_pad(bytes: &[u8]) -> [u8; WORD] {
+    let mut word = [0u8; WORD];
+    let start = WORD - bytes.len();
+    word[start..].copy_from_slice(bytes);
+    word
+}
+
+/// Left-pads a signed big-endian value into a single 32-byte word, sign-extending with `0xff`
+/// when negative instead of zero-padding.
+pub(crate) fn left_pad_signed(bytes: &[u8], negative: bool) -> [u8; WORD] {
+    let fill = if negative { 0xff } else { 0x00 };
+    let mut word = [fill; WORD];
+    let start = WORD - bytes.len();
+    word[start..].copy_from_slice(bytes);
+    word
+}
+
+/// Rounds `len` up to the next multiple of 32, the padded length of a dynamic value's data.
+pub(crate) fn padded_len(len: usize) -> usize {
+    len.div_ceil(WORD) * WORD
+}
+
+/// Reads the big-endian offset/length stored in a 32-byte word as a `usize`.
+///
+/// Solidity words are 256 bits, but real offsets and lengths always fit in a `u64`, so only
+/// the trailing 8 bytes are interpreted.
+pub(crate) fn read_usize(word: &[u8]) -> usize {
+    BigEndian::read_u64(&word[WORD - 8..WORD]) as usize
+}
+
+/// Writes `value` into the trailing 8 bytes of a 32-byte word, matching `read_usize`.
+pub(crate) fn write_usize(word: &mut [u8], value: usize) {
+    BigEndian::write_u64(&mut word[WORD - 8..WORD], value as u64);
+}